@@ -1,5 +1,5 @@
-use chrono::{Duration, Utc};
-use chrono_tz::Europe::Warsaw;
+use chrono::{DateTime, Duration, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::{Europe::Warsaw, Tz};
 use diqwest::{error::Error as DiqwestError, WithDigestAuth};
 use reqwest::{Client as ReqwestClient, Error as ReqwestError, Url};
 use serde::de::DeserializeOwned;
@@ -13,6 +13,45 @@ const USERNAME: &str = "android-mpk";
 const PASSWORD: &str = "g5crehAfUCh4Wust";
 const SQL_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
+/// Strips stray whitespace that sometimes shows up inside an otherwise well-formed timestamp.
+fn strip_spaces(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+fn parse_sql_naive_datetime<E: serde::de::Error>(s: &str) -> Result<NaiveDateTime, E> {
+    NaiveDateTime::parse_from_str(&strip_spaces(s), SQL_DATE_FORMAT).map_err(serde::de::Error::custom)
+}
+
+/// Resolves a naive local time to `Europe/Warsaw`, preferring the earliest valid offset over a
+/// DST boundary instead of rejecting the whole response for an ambiguous or skipped local time.
+fn resolve_warsaw_datetime(naive: NaiveDateTime) -> DateTime<Tz> {
+    let local = naive.and_local_timezone(Warsaw);
+    local
+        .earliest()
+        .or_else(|| local.latest())
+        .unwrap_or_else(|| Warsaw.from_utc_datetime(&naive))
+}
+
+/// Deserializes an SQL-style timestamp into a `DateTime<Tz>` anchored to `Europe/Warsaw`.
+fn deserialize_sql_datetime<'de, D>(deserializer: D) -> Result<DateTime<Tz>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    let naive = parse_sql_naive_datetime(&raw)?;
+    Ok(resolve_warsaw_datetime(naive))
+}
+
+/// Deserializes a course-schedule entry's time-of-day into a `NaiveTime`.
+fn deserialize_course_time<'de, D>(deserializer: D) -> Result<NaiveTime, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    let naive = parse_sql_naive_datetime(&raw)?;
+    Ok(naive.time())
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum VehicleType {
@@ -25,29 +64,29 @@ pub enum VehicleType {
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Bus {
     #[serde(alias = "v")]
-    code: i32,
+    pub(crate) code: i32,
     #[serde(alias = "c")]
-    course: i32,
+    pub(crate) course: i32,
     #[serde(rename = "x")]
-    latitude: f32,
+    pub(crate) latitude: f32,
     #[serde(rename = "y")]
-    longitude: f32,
+    pub(crate) longitude: f32,
     #[serde(alias = "l")]
-    line: String,
+    pub(crate) line: String,
     #[serde(rename = "type", alias = "t")]
-    vehicle_type: VehicleType,
+    pub(crate) vehicle_type: VehicleType,
     #[serde(alias = "s")]
-    symbol: String,
+    pub(crate) symbol: String,
     #[serde(alias = "d")]
-    direction: String,
+    pub(crate) direction: String,
     #[serde(alias = "e")]
-    delay: i32,
+    pub(crate) delay: i32,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct BusList {
-    timestamp: String,
-    buses: Vec<Bus>,
+    pub(crate) timestamp: DateTime<Tz>,
+    pub(crate) buses: Vec<Bus>,
 }
 
 impl<'de> Deserialize<'de> for BusList {
@@ -71,9 +110,11 @@ impl<'de> Deserialize<'de> for BusList {
             where
                 A: SeqAccess<'de>,
             {
-                let timestamp: String = seq
+                let raw_timestamp: String = seq
                     .next_element()?
                     .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+                let timestamp =
+                    resolve_warsaw_datetime(parse_sql_naive_datetime::<A::Error>(&raw_timestamp)?);
 
                 let mut buses = Vec::new();
                 while let Some(bus) = seq.next_element()? {
@@ -133,13 +174,13 @@ where
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct BusStop {
     #[serde(rename = "l")]
-    label: String,
+    pub(crate) label: String,
     #[serde(rename = "d")]
-    direction: String,
-    #[serde(rename = "t")]
-    time: String,
+    pub(crate) direction: String,
+    #[serde(rename = "t", deserialize_with = "deserialize_sql_datetime")]
+    pub(crate) time: DateTime<Tz>,
     #[serde(rename = "c")]
-    course: u32,
+    pub(crate) course: u32,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
@@ -156,8 +197,71 @@ pub struct CourseInfo {
 pub struct Course {
     #[serde(rename = "s")]
     symbol: String,
-    #[serde(rename = "t")]
-    time: String,
+    #[serde(rename = "t", deserialize_with = "deserialize_course_time")]
+    time: NaiveTime,
+}
+
+impl CourseInfo {
+    /// Decodes the Google-encoded polyline in [`CourseInfo::encoded`] into `(lat, lng)` points.
+    pub fn shape(&self) -> Vec<(f64, f64)> {
+        let bytes: Vec<u8> = self
+            .encoded
+            .bytes()
+            .filter(|b| !b.is_ascii_whitespace())
+            .collect();
+
+        let mut points = Vec::new();
+        let mut index = 0;
+        let mut lat = 0i64;
+        let mut lng = 0i64;
+
+        while index < bytes.len() {
+            let Some(lat_delta) = Self::decode_value(&bytes, &mut index) else {
+                break;
+            };
+            let Some(lng_delta) = Self::decode_value(&bytes, &mut index) else {
+                break;
+            };
+
+            lat += lat_delta;
+            lng += lng_delta;
+
+            points.push((lat as f64 / 1e5, lng as f64 / 1e5));
+        }
+
+        points
+    }
+
+    /// Decodes a single zig-zag varint starting at `*index`, advancing it past the bytes consumed.
+    fn decode_value(bytes: &[u8], index: &mut usize) -> Option<i64> {
+        let mut result: i64 = 0;
+        let mut shift = 0;
+
+        loop {
+            // Each chunk contributes 5 bits at `shift`; past shift 57 that would spill past
+            // i64's top bit, so reject rather than silently wrapping into garbage coordinates.
+            if shift > 57 {
+                return None;
+            }
+
+            let byte = *bytes.get(*index)?;
+            *index += 1;
+
+            let chunk = (byte as i64 - 63) & 0x1f;
+            result |= chunk << shift;
+            shift += 5;
+
+            if (byte as i64 - 63) < 0x20 {
+                break;
+            }
+        }
+
+        Some(if result & 1 != 0 {
+            !(result >> 1)
+        } else {
+            result >> 1
+        })
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
@@ -174,8 +278,8 @@ pub struct PostPlate {
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct PostPlateTimeTable {
-    #[serde(rename = "t")]
-    vaild_from: String,
+    #[serde(rename = "t", deserialize_with = "deserialize_sql_datetime")]
+    vaild_from: DateTime<Tz>,
     #[serde(rename = "v")]
     values: Vec<PostPlateTableByDirection>,
 }
@@ -341,6 +445,20 @@ fn test_bus_full_from_json() {
     let _v: Bus = serde_json::from_str(json).unwrap();
 }
 
+#[test]
+fn test_bus_stop_info_during_dst_fallback() {
+    use chrono::Timelike;
+
+    // 2025-10-26 02:30 is ambiguous in Europe/Warsaw (clocks fall back from 03:00 to 02:00);
+    // a naive `.single()` lookup would reject this otherwise well-formed response.
+    let json = r#"[{ "l": "250", "d": "20362", "t": "2025-10-26 02:30:00", "c": 25622727 }]"#;
+
+    let stops: Vec<BusStop> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(stops[0].time.hour(), 2);
+    assert_eq!(stops[0].time.minute(), 30);
+}
+
 #[test]
 fn test_bus_stop_info() {
     let json = r#"
@@ -413,3 +531,55 @@ fn test_courses_info() {
 ]"#;
     let _v: Vec<CourseInfo> = serde_json::from_str(json).unwrap();
 }
+
+#[test]
+fn test_course_info_shape() {
+    let course = CourseInfo {
+        course: 1,
+        encoded: "_p~iF~ps|U_ulLnnqC_mqNvxq`@".to_string(),
+        r: Vec::new(),
+    };
+
+    assert_eq!(
+        course.shape(),
+        vec![(38.5, -120.2), (40.7, -120.95), (43.252, -126.453)]
+    );
+}
+
+#[test]
+fn test_course_info_shape_strips_whitespace() {
+    let course = CourseInfo {
+        course: 1,
+        encoded: "_p~iF~ps|U _ulLnnqC _mqNvxq`@".to_string(),
+        r: Vec::new(),
+    };
+
+    assert_eq!(
+        course.shape(),
+        vec![(38.5, -120.2), (40.7, -120.95), (43.252, -126.453)]
+    );
+}
+
+#[test]
+fn test_course_info_shape_truncated() {
+    let course = CourseInfo {
+        course: 1,
+        encoded: "_p~iF~ps|U_ulLnnqC_mqN".to_string(),
+        r: Vec::new(),
+    };
+
+    assert_eq!(course.shape(), vec![(38.5, -120.2), (40.7, -120.95)]);
+}
+
+#[test]
+fn test_course_info_shape_rejects_over_long_varint() {
+    // 13 continuation bytes (each with the high bit set) push `shift` past 57 with more input
+    // still available; this must stop cleanly instead of overflowing into garbage coordinates.
+    let course = CourseInfo {
+        course: 1,
+        encoded: "~".repeat(13),
+        r: Vec::new(),
+    };
+
+    assert_eq!(course.shape(), Vec::<(f64, f64)>::new());
+}
@@ -1,11 +1,12 @@
 use crate::utils::{empty_string_as_none, trim_string};
 use chrono::serde::ts_milliseconds;
 use chrono::{DateTime, Utc};
-use futures::{stream, StreamExt};
 use reqwest::{Client as ReqwestClient, Error};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::fmt::{Debug, Display};
+use std::time::Duration;
+use thiserror::Error as ThisError;
 
 const API_URLS: [&str; 3] = [
     "https://api.dla.sims.pl",
@@ -13,107 +14,168 @@ const API_URLS: [&str; 3] = [
     "https://api.dlugoleka.mp.sims.pl",
 ];
 
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A single host's failed attempt, kept around for diagnostics.
+#[derive(Debug, ThisError)]
+#[error("{host}: {error}")]
+pub struct HostError {
+    pub host: String,
+    #[source]
+    pub error: Error,
+}
+
+/// Every candidate host failed or timed out; carries one [`HostError`] per host that was tried.
+#[derive(Debug, ThisError)]
+#[error("all {} candidate host(s) failed", .0.len())]
+pub struct FetchErrors(pub Vec<HostError>);
+
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Bus {
-    side_number: String,
+    pub(crate) side_number: String,
     #[serde(with = "ts_milliseconds", rename = "recieveTime")]
-    receive_time: DateTime<Utc>,
-    is_connected: bool,
-    latitude: f32,
-    longitude: f32,
-    previous_latitude: f32,
-    previous_longitude: f32,
+    pub(crate) receive_time: DateTime<Utc>,
+    pub(crate) is_connected: bool,
+    pub(crate) latitude: f32,
+    pub(crate) longitude: f32,
+    pub(crate) previous_latitude: f32,
+    pub(crate) previous_longitude: f32,
     #[serde(default, deserialize_with = "empty_string_as_none")]
-    brigade: Option<String>,
+    pub(crate) brigade: Option<String>,
     #[serde(default, deserialize_with = "empty_string_as_none")]
-    direction: Option<String>,
+    pub(crate) direction: Option<String>,
     /// Exists when `is_connected` is true
     #[serde(default, deserialize_with = "empty_string_as_none")]
-    line: Option<String>,
-    delay: Option<i32>,
+    pub(crate) line: Option<String>,
+    pub(crate) delay: Option<i32>,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BusStop {
     #[serde(rename = "busStopCode")]
-    code: String,
+    pub(crate) code: String,
     #[serde(rename = "busStopName")]
-    name: String,
+    pub(crate) name: String,
     #[serde(rename = "busStopLatitude")]
-    latitude: f32,
+    pub(crate) latitude: f32,
     #[serde(rename = "busStopLongitude")]
-    longitude: f32,
+    pub(crate) longitude: f32,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Timetable {
-    line: TimetableLine,
-    direction: TimetableDirection,
+    pub(crate) line: TimetableLine,
+    pub(crate) direction: TimetableDirection,
     #[serde(with = "ts_milliseconds")]
-    timetable_departure_time: DateTime<Utc>,
-    show_type: i32,
-    departure_hide: bool,
+    pub(crate) timetable_departure_time: DateTime<Utc>,
+    pub(crate) show_type: i32,
+    pub(crate) departure_hide: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TimetableLine {
-    id: u32,
-    name: String,
+    pub(crate) id: u32,
+    pub(crate) name: String,
     #[serde(deserialize_with = "trim_string")]
-    number: String,
+    pub(crate) number: String,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TimetableDirection {
-    id: u32,
-    name: String,
+    pub(crate) id: u32,
+    pub(crate) name: String,
 }
 
 #[derive(Clone, Debug)]
 pub struct Client {
     client: ReqwestClient,
+    hosts: Vec<String>,
+    timeout: Duration,
 }
 
-impl Client {
-    pub fn new() -> Self {
+impl Default for Client {
+    fn default() -> Self {
         Self {
             client: ReqwestClient::new(),
+            hosts: API_URLS.iter().map(|host| host.to_string()).collect(),
+            timeout: DEFAULT_TIMEOUT,
         }
     }
+}
 
-    async fn get_data<T: DeserializeOwned + Debug>(&self, endpoint: &str) -> (Vec<T>, Vec<Error>) {
-        let results: Vec<Result<Vec<T>, Error>> = stream::iter(API_URLS)
-            .then(|hostname| {
-                let url = format!("{}/{}", hostname, endpoint);
-                async move { self.client.get(&url).send().await?.json::<Vec<T>>().await }
-            })
-            .collect()
+impl Client {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the mirrors tried, in priority order. The default is [`API_URLS`].
+    pub fn with_hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.hosts = hosts.into_iter().map(|host| host.to_string()).collect();
+        self
+    }
+
+    /// Overrides the per-host request timeout. Defaults to 5 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Tries each configured host in order, returning the first successful response. Hosts
+    /// serve the same data, so there is no point merging them: a later success would only
+    /// duplicate what an earlier mirror already returned. Every failure/timeout along the way is
+    /// collected into the returned [`FetchErrors`] if all hosts are exhausted.
+    async fn get_data<T: DeserializeOwned + Debug>(
+        &self,
+        endpoint: &str,
+    ) -> Result<Vec<T>, FetchErrors> {
+        let mut errors = Vec::new();
+
+        for host in &self.hosts {
+            let url = format!("{}/{}", host, endpoint);
+            let result: Result<Vec<T>, Error> = async {
+                self.client
+                    .get(&url)
+                    .timeout(self.timeout)
+                    .send()
+                    .await?
+                    .json::<Vec<T>>()
+                    .await
+            }
             .await;
 
-        let (buses, errors): (Vec<_>, Vec<_>) = results.into_iter().partition(Result::is_ok);
+            match result {
+                Ok(items) => return Ok(items),
+                Err(error) => errors.push(HostError {
+                    host: host.clone(),
+                    error,
+                }),
+            }
+        }
 
-        let buses = buses.into_iter().flat_map(Result::unwrap).collect();
-        let errors = errors.into_iter().map(Result::unwrap_err).collect();
-        (buses, errors)
+        Err(FetchErrors(errors))
     }
 
-    pub async fn get_buses(&self) -> (Vec<Bus>, Vec<Error>) {
+    pub async fn get_buses(&self) -> Result<Vec<Bus>, FetchErrors> {
         self.get_data("vehicles").await
     }
 
-    pub async fn get_bus_stops(&self) -> (Vec<BusStop>, Vec<Error>) {
+    pub async fn get_bus_stops(&self) -> Result<Vec<BusStop>, FetchErrors> {
         self.get_data("timetables/busStops").await
     }
 
     pub async fn get_timetable<T: Display>(
         &self,
         bus_stop_code: T,
-    ) -> (Vec<Timetable>, Vec<Error>) {
+    ) -> Result<Vec<Timetable>, FetchErrors> {
         let endpoint = format!("timetables/busStops/{}", bus_stop_code);
         self.get_data(&endpoint).await
     }
@@ -0,0 +1,283 @@
+//! GTFS-Realtime / GTFS-static export, gated behind the `gtfs` feature.
+
+use crate::providers::{Stop, Vehicle, VehicleKind};
+use gtfs_rt::{
+    feed_header::Incrementality,
+    trip_descriptor::ScheduleRelationship,
+    trip_update::{StopTimeEvent, StopTimeUpdate},
+    FeedEntity, FeedHeader, FeedMessage, Position, TripDescriptor, TripUpdate, VehicleDescriptor,
+    VehiclePosition,
+};
+
+const GTFS_REALTIME_VERSION: &str = "2.0";
+
+/// `timestamp` is POSIX time in seconds, as required by the GTFS-Realtime spec.
+///
+/// GTFS-Realtime has no room for delay magnitude on `VehiclePosition` itself, so `Vehicle.delay`
+/// isn't carried here; build a paired [`trip_update_for_vehicle`] entity once a stop is known.
+pub fn vehicle_positions_feed(vehicles: &[Vehicle], timestamp: u64) -> FeedMessage {
+    let header = FeedHeader {
+        gtfs_realtime_version: GTFS_REALTIME_VERSION.to_string(),
+        incrementality: Some(Incrementality::FullDataset as i32),
+        timestamp: Some(timestamp),
+    };
+
+    let entity = vehicles
+        .iter()
+        .enumerate()
+        .map(|(index, vehicle)| FeedEntity {
+            id: format!("vehicle-{index}"),
+            vehicle: Some(VehiclePosition {
+                trip: Some(TripDescriptor {
+                    route_id: Some(vehicle.line.clone()),
+                    schedule_relationship: Some(ScheduleRelationship::Scheduled as i32),
+                    ..Default::default()
+                }),
+                vehicle: Some(VehicleDescriptor {
+                    id: Some(vehicle.id.clone()),
+                    ..Default::default()
+                }),
+                position: Some(Position {
+                    latitude: vehicle.lat as f32,
+                    longitude: vehicle.lng as f32,
+                    bearing: vehicle.heading,
+                    ..Default::default()
+                }),
+                timestamp: Some(timestamp),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .collect();
+
+    FeedMessage { header, entity }
+}
+
+/// Builds a `TripUpdate` [`FeedEntity`] carrying `vehicle`'s delay for one scheduled stop. Returns
+/// `None` if `vehicle` has no delay to report. `stop_sequence` identifies the stop within the
+/// trip, per the GTFS-Realtime spec. Both come from the caller: [`crate::providers::Departure`]
+/// carries neither a stop id nor a stop sequence.
+pub fn trip_update_for_vehicle(
+    vehicle: &Vehicle,
+    stop_id: &str,
+    stop_sequence: u32,
+    timestamp: u64,
+) -> Option<FeedEntity> {
+    let delay = vehicle.delay?;
+
+    Some(FeedEntity {
+        id: format!("trip_update-{}", vehicle.id),
+        trip_update: Some(TripUpdate {
+            trip: TripDescriptor {
+                route_id: Some(vehicle.line.clone()),
+                schedule_relationship: Some(ScheduleRelationship::Scheduled as i32),
+                ..Default::default()
+            },
+            vehicle: Some(VehicleDescriptor {
+                id: Some(vehicle.id.clone()),
+                ..Default::default()
+            }),
+            stop_time_update: vec![StopTimeUpdate {
+                stop_sequence: Some(stop_sequence),
+                stop_id: Some(stop_id.to_string()),
+                arrival: Some(StopTimeEvent {
+                    delay: Some(delay.num_seconds() as i32),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            timestamp: Some(timestamp),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renders the GTFS static `stops.txt` for the given stops.
+pub fn stops_csv(stops: &[Stop]) -> String {
+    let mut csv = String::from("stop_id,stop_name,stop_lat,stop_lon\n");
+
+    for stop in stops {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            csv_field(&stop.id),
+            csv_field(&stop.label),
+            stop.lat.map(|v| v.to_string()).unwrap_or_default(),
+            stop.lng.map(|v| v.to_string()).unwrap_or_default(),
+        ));
+    }
+
+    csv
+}
+
+/// Renders the GTFS static `routes.txt`, deriving one row per distinct line seen across
+/// `vehicles`.
+pub fn routes_csv(vehicles: &[Vehicle]) -> String {
+    let mut seen = std::collections::HashSet::new();
+    let mut csv = String::from("route_id,route_short_name,route_type\n");
+
+    for vehicle in vehicles {
+        if !seen.insert(vehicle.line.clone()) {
+            continue;
+        }
+
+        // GTFS route_type: 0 = tram, 3 = bus.
+        let route_type = match vehicle.kind {
+            VehicleKind::Bus => 3,
+            VehicleKind::Tram => 0,
+        };
+
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            csv_field(&vehicle.line),
+            csv_field(&vehicle.line),
+            route_type
+        ));
+    }
+
+    csv
+}
+
+#[test]
+fn test_vehicle_positions_feed() {
+    let vehicles = vec![Vehicle {
+        id: "1007".to_string(),
+        line: "K".to_string(),
+        lat: 51.095,
+        lng: 16.962,
+        heading: Some(90.0),
+        delay: None,
+        kind: VehicleKind::Bus,
+        connected: true,
+        observed_at: chrono::Utc::now(),
+    }];
+
+    let feed = vehicle_positions_feed(&vehicles, 1_700_000_000);
+
+    assert_eq!(feed.header.timestamp, Some(1_700_000_000));
+    assert_eq!(feed.entity.len(), 1);
+
+    let vehicle_position = feed.entity[0].vehicle.as_ref().unwrap();
+    assert_eq!(
+        vehicle_position.vehicle.as_ref().unwrap().id.as_deref(),
+        Some("1007")
+    );
+    assert_eq!(
+        vehicle_position.trip.as_ref().unwrap().route_id.as_deref(),
+        Some("K")
+    );
+    assert_eq!(vehicle_position.position.as_ref().unwrap().bearing, Some(90.0));
+}
+
+#[test]
+fn test_trip_update_for_vehicle() {
+    let vehicle = Vehicle {
+        id: "1007".to_string(),
+        line: "K".to_string(),
+        lat: 51.095,
+        lng: 16.962,
+        heading: None,
+        delay: Some(chrono::Duration::seconds(90)),
+        kind: VehicleKind::Bus,
+        connected: true,
+        observed_at: chrono::Utc::now(),
+    };
+
+    let entity = trip_update_for_vehicle(&vehicle, "18360", 3, 1_700_000_000).unwrap();
+    let trip_update = entity.trip_update.unwrap();
+
+    assert_eq!(trip_update.trip.route_id.as_deref(), Some("K"));
+    assert_eq!(
+        trip_update.vehicle.as_ref().unwrap().id.as_deref(),
+        Some("1007")
+    );
+    assert_eq!(trip_update.stop_time_update.len(), 1);
+    let stop_time_update = &trip_update.stop_time_update[0];
+    assert_eq!(stop_time_update.stop_id.as_deref(), Some("18360"));
+    assert_eq!(stop_time_update.stop_sequence, Some(3));
+    assert_eq!(stop_time_update.arrival.as_ref().unwrap().delay, Some(90));
+}
+
+#[test]
+fn test_trip_update_for_vehicle_without_delay() {
+    let vehicle = Vehicle {
+        id: "1007".to_string(),
+        line: "K".to_string(),
+        lat: 51.095,
+        lng: 16.962,
+        heading: None,
+        delay: None,
+        kind: VehicleKind::Bus,
+        connected: true,
+        observed_at: chrono::Utc::now(),
+    };
+
+    assert!(trip_update_for_vehicle(&vehicle, "18360", 3, 1_700_000_000).is_none());
+}
+
+#[test]
+fn test_stops_csv() {
+    let stops = vec![Stop {
+        id: "18360".to_string(),
+        label: "Grzybowa, Ptasia".to_string(),
+        lat: Some(51.1589),
+        lng: Some(16.8532),
+    }];
+
+    assert_eq!(
+        stops_csv(&stops),
+        "stop_id,stop_name,stop_lat,stop_lon\n18360,\"Grzybowa, Ptasia\",51.1589,16.8532\n"
+    );
+}
+
+#[test]
+fn test_routes_csv_dedupes_lines() {
+    let vehicles = vec![
+        Vehicle {
+            id: "1".to_string(),
+            line: "K".to_string(),
+            lat: 0.0,
+            lng: 0.0,
+            heading: None,
+            delay: None,
+            kind: VehicleKind::Bus,
+            connected: true,
+            observed_at: chrono::Utc::now(),
+        },
+        Vehicle {
+            id: "2".to_string(),
+            line: "K".to_string(),
+            lat: 0.0,
+            lng: 0.0,
+            heading: None,
+            delay: None,
+            kind: VehicleKind::Bus,
+            connected: true,
+            observed_at: chrono::Utc::now(),
+        },
+        Vehicle {
+            id: "3".to_string(),
+            line: "N".to_string(),
+            lat: 0.0,
+            lng: 0.0,
+            heading: None,
+            delay: None,
+            kind: VehicleKind::Tram,
+            connected: true,
+            observed_at: chrono::Utc::now(),
+        },
+    ];
+
+    assert_eq!(
+        routes_csv(&vehicles),
+        "route_id,route_short_name,route_type\nK,K,3\nN,N,0\n"
+    );
+}
@@ -0,0 +1,257 @@
+//! Normalized, backend-agnostic view over the [`crate::clients`] implementations.
+
+use crate::clients::{mpk_wroc, sims};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProviderError {
+    #[error(transparent)]
+    Sims(#[from] sims::FetchErrors),
+    #[error(transparent)]
+    MpkWroc(#[from] mpk_wroc::ClientError),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VehicleKind {
+    Bus,
+    Tram,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vehicle {
+    pub id: String,
+    pub line: String,
+    pub lat: f64,
+    pub lng: f64,
+    pub heading: Option<f32>,
+    pub delay: Option<Duration>,
+    pub kind: VehicleKind,
+    pub connected: bool,
+    /// When this position was recorded, so callers can derive real elapsed time between snapshots.
+    pub observed_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stop {
+    pub id: String,
+    pub label: String,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Departure {
+    pub line: String,
+    pub direction: String,
+    pub scheduled_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait TransitProvider {
+    async fn vehicles(&self) -> Result<Vec<Vehicle>, ProviderError>;
+    async fn stops(&self) -> Result<Vec<Stop>, ProviderError>;
+    async fn departures(&self, stop_id: &str) -> Result<Vec<Departure>, ProviderError>;
+}
+
+fn sims_bus_to_vehicle(bus: sims::Bus) -> Vehicle {
+    Vehicle {
+        id: bus.side_number,
+        line: bus.line.unwrap_or_default(),
+        lat: bus.latitude as f64,
+        lng: bus.longitude as f64,
+        heading: None,
+        delay: bus.delay.map(|ms| Duration::milliseconds(ms as i64)),
+        kind: VehicleKind::Bus,
+        connected: bus.is_connected,
+        observed_at: bus.receive_time,
+    }
+}
+
+fn sims_stop_to_stop(stop: sims::BusStop) -> Stop {
+    Stop {
+        id: stop.code,
+        label: stop.name,
+        lat: Some(stop.latitude as f64),
+        lng: Some(stop.longitude as f64),
+    }
+}
+
+fn sims_timetable_to_departure(timetable: sims::Timetable) -> Departure {
+    Departure {
+        line: timetable.line.number,
+        direction: timetable.direction.name,
+        scheduled_at: timetable.timetable_departure_time,
+    }
+}
+
+fn mpk_bus_to_vehicle(bus: mpk_wroc::Bus, observed_at: DateTime<Utc>) -> Vehicle {
+    Vehicle {
+        id: bus.code.to_string(),
+        line: bus.line,
+        // mpk_wroc::Bus.latitude/longitude are named after the API's `x`/`y` fields, which are
+        // longitude/latitude respectively.
+        lat: bus.longitude as f64,
+        lng: bus.latitude as f64,
+        heading: None,
+        delay: Some(Duration::seconds(bus.delay as i64)),
+        kind: match bus.vehicle_type {
+            mpk_wroc::VehicleType::Bus => VehicleKind::Bus,
+            mpk_wroc::VehicleType::Tram => VehicleKind::Tram,
+        },
+        // getPositions only lists vehicles that are actively broadcasting.
+        connected: true,
+        observed_at,
+    }
+}
+
+fn mpk_stop_to_departure(stop: mpk_wroc::BusStop) -> Departure {
+    Departure {
+        line: stop.label,
+        direction: stop.direction,
+        scheduled_at: stop.time.with_timezone(&Utc),
+    }
+}
+
+#[async_trait]
+impl TransitProvider for sims::Client {
+    async fn vehicles(&self) -> Result<Vec<Vehicle>, ProviderError> {
+        let buses = self.get_buses().await?;
+        Ok(buses.into_iter().map(sims_bus_to_vehicle).collect())
+    }
+
+    async fn stops(&self) -> Result<Vec<Stop>, ProviderError> {
+        let stops = self.get_bus_stops().await?;
+        Ok(stops.into_iter().map(sims_stop_to_stop).collect())
+    }
+
+    async fn departures(&self, stop_id: &str) -> Result<Vec<Departure>, ProviderError> {
+        let timetables = self.get_timetable(stop_id).await?;
+        Ok(timetables
+            .into_iter()
+            .map(sims_timetable_to_departure)
+            .collect())
+    }
+}
+
+#[async_trait]
+impl TransitProvider for mpk_wroc::Client {
+    async fn vehicles(&self) -> Result<Vec<Vehicle>, ProviderError> {
+        let bus_list = self.get_buses().await?;
+        let observed_at = bus_list.timestamp.with_timezone(&Utc);
+        Ok(bus_list
+            .buses
+            .into_iter()
+            .map(|bus| mpk_bus_to_vehicle(bus, observed_at))
+            .collect())
+    }
+
+    async fn stops(&self) -> Result<Vec<Stop>, ProviderError> {
+        // The impk.mpk.wroc.pl client has no endpoint listing every stop, and neither
+        // `get_post_info` nor `get_post_plate` returns stop coordinates, so no stops are listed.
+        Ok(Vec::new())
+    }
+
+    async fn departures(&self, stop_id: &str) -> Result<Vec<Departure>, ProviderError> {
+        let stops = self.get_post_info(stop_id).await?;
+        Ok(stops.into_iter().map(mpk_stop_to_departure).collect())
+    }
+}
+
+#[test]
+fn test_sims_bus_to_vehicle() {
+    let receive_time = chrono::Utc::now();
+    let bus = sims::Bus {
+        side_number: "1007".to_string(),
+        receive_time,
+        is_connected: true,
+        latitude: 51.095,
+        longitude: 16.962,
+        previous_latitude: 51.095,
+        previous_longitude: 16.962,
+        brigade: Some("90701".to_string()),
+        direction: None,
+        line: Some("K".to_string()),
+        delay: Some(27_000),
+    };
+
+    let vehicle = sims_bus_to_vehicle(bus);
+
+    assert_eq!(vehicle.id, "1007");
+    assert_eq!(vehicle.line, "K");
+    assert_eq!(vehicle.kind, VehicleKind::Bus);
+    assert!(vehicle.connected);
+    assert_eq!(vehicle.delay, Some(Duration::milliseconds(27_000)));
+    assert_eq!(vehicle.observed_at, receive_time);
+}
+
+#[test]
+fn test_mpk_bus_to_vehicle() {
+    let bus = mpk_wroc::Bus {
+        code: 8418,
+        course: 25626631,
+        latitude: 17.051289,
+        longitude: 51.11734,
+        line: "N".to_string(),
+        vehicle_type: mpk_wroc::VehicleType::Tram,
+        symbol: "20903".to_string(),
+        direction: "29324".to_string(),
+        delay: 27,
+    };
+    let observed_at = chrono::Utc::now();
+
+    let vehicle = mpk_bus_to_vehicle(bus, observed_at);
+
+    assert_eq!(vehicle.id, "8418");
+    assert_eq!(vehicle.line, "N");
+    assert_eq!(vehicle.lat, 51.11734_f32 as f64);
+    assert_eq!(vehicle.lng, 17.051289_f32 as f64);
+    assert_eq!(vehicle.kind, VehicleKind::Tram);
+    assert!(vehicle.connected);
+    assert_eq!(vehicle.delay, Some(Duration::seconds(27)));
+    assert_eq!(vehicle.observed_at, observed_at);
+}
+
+#[test]
+fn test_sims_stop_to_stop() {
+    let stop = sims::BusStop {
+        code: "18360".to_string(),
+        name: "Grzybowa".to_string(),
+        latitude: 51.1589,
+        longitude: 16.8532,
+    };
+
+    assert_eq!(
+        sims_stop_to_stop(stop),
+        Stop {
+            id: "18360".to_string(),
+            label: "Grzybowa".to_string(),
+            lat: Some(51.1589_f32 as f64),
+            lng: Some(16.8532_f32 as f64),
+        }
+    );
+}
+
+#[test]
+fn test_mpk_stop_to_departure() {
+    use chrono::TimeZone;
+
+    let stop = mpk_wroc::BusStop {
+        label: "250".to_string(),
+        direction: "20362".to_string(),
+        time: chrono_tz::Europe::Warsaw
+            .with_ymd_and_hms(2025, 2, 26, 23, 38, 0)
+            .unwrap(),
+        course: 25622727,
+    };
+
+    let departure = mpk_stop_to_departure(stop);
+
+    assert_eq!(departure.line, "250");
+    assert_eq!(departure.direction, "20362");
+    assert_eq!(
+        departure.scheduled_at,
+        chrono::Utc.with_ymd_and_hms(2025, 2, 26, 22, 38, 0).unwrap()
+    );
+}
@@ -0,0 +1,284 @@
+//! Live-polling vehicle stream built on top of [`crate::providers::TransitProvider`].
+
+use crate::providers::{Departure, ProviderError, Stop, TransitProvider, Vehicle};
+use async_trait::async_trait;
+use futures::stream::{self, Stream};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub min_lng: f64,
+    pub max_lat: f64,
+    pub max_lng: f64,
+}
+
+impl BoundingBox {
+    fn contains(&self, lat: f64, lng: f64) -> bool {
+        lat >= self.min_lat && lat <= self.max_lat && lng >= self.min_lng && lng <= self.max_lng
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct WatchFilter {
+    pub bounding_box: Option<BoundingBox>,
+    pub lines: Option<Vec<String>>,
+}
+
+impl WatchFilter {
+    fn matches(&self, vehicle: &Vehicle) -> bool {
+        if let Some(bbox) = &self.bounding_box {
+            if !bbox.contains(vehicle.lat, vehicle.lng) {
+                return false;
+            }
+        }
+
+        if let Some(lines) = &self.lines {
+            if !lines.iter().any(|line| line == &vehicle.line) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VehicleMotion {
+    pub vehicle: Vehicle,
+    /// Meters per second.
+    pub speed: f64,
+    /// Degrees clockwise from north.
+    pub heading: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VehicleUpdate {
+    Appeared(Vehicle),
+    Moved(VehicleMotion),
+    Disconnected(Vehicle),
+    Vanished(String),
+}
+
+fn distance_meters(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lng1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lng2) = (to.0.to_radians(), to.1.to_radians());
+
+    let dlat = lat2 - lat1;
+    let dlng = lng2 - lng1;
+
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlng / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().asin();
+
+    EARTH_RADIUS_METERS * c
+}
+
+fn bearing_degrees(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lng1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lng2) = (to.0.to_radians(), to.1.to_radians());
+
+    let dlng = lng2 - lng1;
+    let y = dlng.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlng.cos();
+
+    (y.atan2(x).to_degrees() + 360.0) % 360.0
+}
+
+pub fn watch<P>(
+    provider: P,
+    interval: Duration,
+    filter: WatchFilter,
+) -> impl Stream<Item = Result<VehicleUpdate, ProviderError>>
+where
+    P: TransitProvider,
+{
+    struct State<P> {
+        provider: P,
+        filter: WatchFilter,
+        interval: Duration,
+        previous: HashMap<String, Vehicle>,
+        pending: VecDeque<VehicleUpdate>,
+        started: bool,
+    }
+
+    let state = State {
+        provider,
+        filter,
+        interval,
+        previous: HashMap::new(),
+        pending: VecDeque::new(),
+        started: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(update) = state.pending.pop_front() {
+                return Some((Ok(update), state));
+            }
+
+            if state.started {
+                tokio::time::sleep(state.interval).await;
+            }
+            state.started = true;
+
+            let vehicles = match state.provider.vehicles().await {
+                Ok(vehicles) => vehicles,
+                Err(err) => return Some((Err(err), state)),
+            };
+
+            let mut current = HashMap::new();
+            for vehicle in vehicles.into_iter().filter(|v| state.filter.matches(v)) {
+                current.insert(vehicle.id.clone(), vehicle);
+            }
+
+            for (id, vehicle) in &current {
+                match state.previous.get(id) {
+                    None => state.pending.push_back(VehicleUpdate::Appeared(vehicle.clone())),
+                    Some(previous) if previous.connected && !vehicle.connected => {
+                        state
+                            .pending
+                            .push_back(VehicleUpdate::Disconnected(vehicle.clone()));
+                    }
+                    Some(previous)
+                        if previous.lat != vehicle.lat || previous.lng != vehicle.lng =>
+                    {
+                        let from = (previous.lat, previous.lng);
+                        let to = (vehicle.lat, vehicle.lng);
+                        let elapsed = (vehicle.observed_at - previous.observed_at)
+                            .to_std()
+                            .map(|d| d.as_secs_f64())
+                            .unwrap_or_else(|_| state.interval.as_secs_f64());
+                        let speed = if elapsed > 0.0 {
+                            distance_meters(from, to) / elapsed
+                        } else {
+                            0.0
+                        };
+                        let heading = bearing_degrees(from, to);
+
+                        let mut vehicle = vehicle.clone();
+                        vehicle.heading = Some(heading as f32);
+
+                        state.pending.push_back(VehicleUpdate::Moved(VehicleMotion {
+                            vehicle,
+                            speed,
+                            heading,
+                        }));
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            for id in state.previous.keys() {
+                if !current.contains_key(id) {
+                    state.pending.push_back(VehicleUpdate::Vanished(id.clone()));
+                }
+            }
+
+            state.previous = current;
+        }
+    })
+}
+
+#[cfg(test)]
+struct StubProvider {
+    snapshots: std::sync::Mutex<VecDeque<Vec<Vehicle>>>,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl TransitProvider for StubProvider {
+    async fn vehicles(&self) -> Result<Vec<Vehicle>, ProviderError> {
+        Ok(self
+            .snapshots
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("test exhausted its canned snapshots"))
+    }
+
+    async fn stops(&self) -> Result<Vec<Stop>, ProviderError> {
+        Ok(Vec::new())
+    }
+
+    async fn departures(&self, _stop_id: &str) -> Result<Vec<Departure>, ProviderError> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+fn test_vehicle_at(
+    id: &str,
+    lat: f64,
+    lng: f64,
+    connected: bool,
+    observed_at: chrono::DateTime<chrono::Utc>,
+) -> Vehicle {
+    Vehicle {
+        id: id.to_string(),
+        line: "K".to_string(),
+        lat,
+        lng,
+        heading: None,
+        delay: None,
+        kind: crate::providers::VehicleKind::Bus,
+        connected,
+        observed_at,
+    }
+}
+
+#[test]
+fn test_distance_meters() {
+    let meters = distance_meters((0.0, 0.0), (1.0, 0.0));
+    assert!((meters - 111_195.0).abs() < 100.0);
+}
+
+#[test]
+fn test_bearing_degrees_due_north() {
+    let degrees = bearing_degrees((0.0, 0.0), (1.0, 0.0));
+    assert!(degrees.abs() < 0.01);
+}
+
+#[tokio::test]
+async fn test_watch_yields_appeared_moved_vanished() {
+    use futures::StreamExt;
+
+    let t0 = chrono::Utc::now();
+    // Real spacing between snapshots (600ms) deliberately differs from the polling `interval`
+    // (200ms) below, so a speed derived from the nominal interval would be wrong.
+    let t1 = t0 + chrono::Duration::milliseconds(600);
+
+    let provider = StubProvider {
+        snapshots: std::sync::Mutex::new(VecDeque::from(vec![
+            vec![test_vehicle_at("1007", 51.0, 17.0, true, t0)],
+            vec![test_vehicle_at("1007", 51.001, 17.0, true, t1)],
+            vec![],
+        ])),
+    };
+
+    let interval = Duration::from_millis(200);
+    let updates: Vec<_> = watch(provider, interval, WatchFilter::default())
+        .take(3)
+        .collect()
+        .await;
+
+    let appeared = updates[0].as_ref().unwrap();
+    assert!(matches!(appeared, VehicleUpdate::Appeared(v) if v.id == "1007"));
+
+    let moved = updates[1].as_ref().unwrap();
+    match moved {
+        VehicleUpdate::Moved(motion) => {
+            let expected_speed = distance_meters((51.0, 17.0), (51.001, 17.0))
+                / (t1 - t0).to_std().unwrap().as_secs_f64();
+            assert_eq!(motion.speed, expected_speed);
+            assert!(motion.speed > 100.0);
+            assert_eq!(motion.vehicle.heading, Some(motion.heading as f32));
+        }
+        other => panic!("expected Moved, got {other:?}"),
+    }
+
+    let vanished = updates[2].as_ref().unwrap();
+    assert!(matches!(vanished, VehicleUpdate::Vanished(id) if id == "1007"));
+}